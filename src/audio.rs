@@ -0,0 +1,237 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+
+use gst;
+use gst::prelude::*;
+use gst::State;
+use gst_app;
+
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+
+use wvr_data::types::DataHolder;
+use wvr_data::types::InputProvider;
+
+/// Size of the analysis window and FFT. Kept a power of two so the transform
+/// stays fast; the spectrum we expose has `WINDOW_SIZE / 2 + 1` bins.
+const WINDOW_SIZE: usize = 1024;
+
+/// Sample rate we force the capture caps to, so the bin-to-frequency mapping is
+/// stable regardless of the default rate the hardware would negotiate.
+const SAMPLE_RATE: i32 = 44_100;
+
+/// Double-buffered analysis result, mirroring `CamProvider`'s `video_buffer`:
+/// the capture thread writes the latest spectrum and the render thread drains
+/// it, clearing `spectrum` when `get` invalidates so stale frames are dropped.
+struct AudioBuffer {
+    spectrum: Option<Vec<f32>>,
+    rms: f32,
+}
+
+pub struct AudioProvider {
+    name: String,
+    audio_buffer: Arc<Mutex<AudioBuffer>>,
+    pipeline: gst::Element,
+}
+
+impl AudioProvider {
+    pub fn new(name: String) -> Result<Self> {
+        gst::init().context("Failed to initialize the gstreamer library")?;
+
+        let audio_buffer = Arc::new(Mutex::new(AudioBuffer {
+            spectrum: None,
+            rms: 0.0,
+        }));
+
+        let pipeline_string = format!(
+            "autoaudiosrc ! audioconvert ! audioresample ! audio/x-raw,format=F32LE,channels=1,rate={:} ! appsink name=appsink async=true sync=false",
+            SAMPLE_RATE
+        );
+
+        let pipeline =
+            gst::parse_launch(&pipeline_string).context("Failed to build gstreamer pipeline")?;
+
+        let sink = pipeline
+            .clone()
+            .dynamic_cast::<gst::Bin>()
+            .expect("Failed to cast the gstreamer pipeline as a gst::Bin element")
+            .get_by_name("appsink")
+            .expect("Failed to retrieve sink from gstreamer pipeline.");
+
+        let appsink = sink
+            .dynamic_cast::<gst_app::AppSink>()
+            .expect("The sink defined in the pipeline is not an appsink");
+
+        // Pre-compute the Hann window once and plan a single forward FFT that
+        // every hop reuses, the way the audio-preview capture path does.
+        let hann: Vec<f32> = (0..WINDOW_SIZE)
+            .map(|i| {
+                let phase = std::f32::consts::PI * 2.0 * i as f32 / (WINDOW_SIZE as f32 - 1.0);
+                0.5 * (1.0 - phase.cos())
+            })
+            .collect();
+
+        let fft = FftPlanner::new().plan_fft_forward(WINDOW_SIZE);
+
+        {
+            let audio_buffer = audio_buffer.clone();
+            let mut ring: VecDeque<f32> = VecDeque::with_capacity(WINDOW_SIZE * 2);
+
+            appsink.set_callbacks(
+                gst_app::AppSinkCallbacks::builder()
+                    .new_sample(move |appsink| {
+                        let sample = match appsink.pull_sample() {
+                            Err(e) => {
+                                println!("{:}", e);
+                                return Err(gst::FlowError::Eos);
+                            }
+                            Ok(sample) => sample,
+                        };
+
+                        let buffer = if let Some(buffer) = sample.get_buffer() {
+                            buffer
+                        } else {
+                            return Err(gst::FlowError::Error);
+                        };
+
+                        let map = if let Ok(map) = buffer.map_readable() {
+                            map
+                        } else {
+                            return Err(gst::FlowError::Error);
+                        };
+
+                        for frame in map.as_slice().chunks_exact(4) {
+                            ring.push_back(f32::from_le_bytes([frame[0], frame[1], frame[2], frame[3]]));
+                        }
+
+                        // Consume the ring one full window at a time; the tail
+                        // shorter than a window is kept for the next callback.
+                        while ring.len() >= WINDOW_SIZE {
+                            let mut signal: Vec<Complex<f32>> = Vec::with_capacity(WINDOW_SIZE);
+                            let mut energy = 0.0f32;
+
+                            for (i, sample) in ring.drain(..WINDOW_SIZE).enumerate() {
+                                energy += sample * sample;
+                                signal.push(Complex::new(sample * hann[i], 0.0));
+                            }
+
+                            fft.process(&mut signal);
+
+                            // Keep only the non-redundant first N/2 + 1 bins and
+                            // express them in decibels so bass/treble sit on a
+                            // perceptually even scale.
+                            let spectrum: Vec<f32> = signal[..WINDOW_SIZE / 2 + 1]
+                                .iter()
+                                .map(|bin| {
+                                    let magnitude = bin.norm() / WINDOW_SIZE as f32;
+                                    20.0 * (magnitude + 1e-9).log10()
+                                })
+                                .collect();
+
+                            let rms = (energy / WINDOW_SIZE as f32).sqrt();
+
+                            match audio_buffer.lock() {
+                                Ok(mut audio_buffer) => {
+                                    audio_buffer.spectrum = Some(spectrum);
+                                    audio_buffer.rms = rms;
+                                }
+                                Err(e) => {
+                                    eprintln!("Could not lock audio buffer, did the main thread panic? \n{:?}", e);
+                                    return Err(gst::FlowError::Error);
+                                }
+                            }
+                        }
+
+                        Ok(gst::FlowSuccess::Ok)
+                    })
+                    .build(),
+            );
+        }
+
+        pipeline
+            .set_state(State::Playing)
+            .context("Failed to start gstreamer pipeline")?;
+
+        Ok(Self {
+            name,
+            audio_buffer,
+            pipeline,
+        })
+    }
+}
+
+impl Drop for AudioProvider {
+    fn drop(&mut self) {
+        if let Err(e) = self.stop() {
+            eprintln!("{:?}", e);
+        }
+    }
+}
+
+impl InputProvider for AudioProvider {
+    fn set_name(&mut self, name: &str) {
+        self.name = name.to_owned();
+    }
+
+    fn provides(&self) -> Vec<String> {
+        vec![self.name.clone(), format!("{:}_rms", self.name)]
+    }
+
+    fn set_property(&mut self, property: &str, _value: &DataHolder) {
+        eprintln!("Set_property unimplemented for {:}", property);
+    }
+
+    fn get(&mut self, uniform_name: &str, invalidate: bool) -> Option<DataHolder> {
+        let rms_name = format!("{:}_rms", self.name);
+
+        if let Ok(mut audio_buffer) = self.audio_buffer.lock() {
+            // The loudness scalar is always available so shaders can bind the
+            // overall level without pulling the whole spectrum.
+            if uniform_name == rms_name {
+                return Some(DataHolder::Float(audio_buffer.rms));
+            }
+
+            if uniform_name == self.name {
+                let result = audio_buffer
+                    .spectrum
+                    .as_ref()
+                    .map(|spectrum| DataHolder::FloatArray(spectrum.clone()));
+
+                if invalidate {
+                    audio_buffer.spectrum = None;
+                }
+
+                return result;
+            }
+        }
+
+        None
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.pipeline
+            .set_state(State::Null)
+            .context("Failed to stop audio capture")?;
+
+        Ok(())
+    }
+
+    fn play(&mut self) -> Result<()> {
+        self.pipeline
+            .set_state(State::Playing)
+            .context("Failed to resume audio capture")?;
+
+        Ok(())
+    }
+
+    fn pause(&mut self) -> Result<()> {
+        self.pipeline
+            .set_state(State::Paused)
+            .context("Failed to pause audio capture")?;
+
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 
@@ -8,6 +10,8 @@ use gst::prelude::*;
 use gst::FlowError;
 use gst::State;
 use gst_app;
+use gst_pbutils;
+use gst_pbutils::prelude::*;
 use gst_video;
 
 use wvr_data::types::Buffer;
@@ -22,15 +26,157 @@ pub enum TextureFormat {
     RGBAU8,
     BGRU8,
     BGRAU8,
+    /// Packed 4:2:2 luma/chroma, two bytes per pixel (`Y0 U Y1 V`).
+    Yuy2,
+    /// Packed 4:2:2 with the luma/chroma order swapped (`U Y0 V Y1`).
+    Uyvy,
+    /// Single-channel 8-bit luminance, as emitted by IR/depth cameras.
+    Gray8,
+}
+
+/// A single supported capability advertised by a camera `Device`.
+///
+/// Width/height are stored as `(min, max)` ranges because v4l2 devices often
+/// expose a continuous range rather than a fixed list, and the framerates are
+/// kept as raw `(numerator, denominator)` fractions so callers can present
+/// them without losing precision.
+#[derive(Clone, Debug)]
+pub struct CamCaps {
+    pub format: String,
+    pub width: (usize, usize),
+    pub height: (usize, usize),
+    pub framerates: Vec<(i32, i32)>,
+}
+
+/// A camera discovered through GStreamer's `DeviceMonitor`.
+///
+/// `path` is whatever is needed to open the device with the platform source
+/// element (the `device` property of `v4l2src` on Linux), and is passed back
+/// into [`CamProvider::new`].
+#[derive(Clone, Debug)]
+pub struct CamDevice {
+    pub display_name: String,
+    pub path: String,
+    pub caps: Vec<CamCaps>,
+}
+
+/// Container/codec combinations a [`CamProvider`] knows how to archive to.
+#[derive(Clone, Copy, Debug)]
+pub enum RecordingProfile {
+    /// H.264 video muxed into an MP4 container.
+    H264Mp4,
+    /// VP9 video muxed into a WebM container.
+    Vp9WebM,
+}
+
+impl RecordingProfile {
+    /// Build the `encodebin` profile describing this container and codec, the
+    /// way the GStreamer encodebin example assembles an
+    /// `EncodingContainerProfile` from a video stream profile.
+    fn to_container_profile(self) -> gst_pbutils::EncodingContainerProfile {
+        let (container, video) = match self {
+            // `qtmux` advertises `video/quicktime, variant=(string)iso`; build
+            // the caps through the builder so the `variant` field is a real
+            // field rather than part of the structure name.
+            RecordingProfile::H264Mp4 => (
+                gst::Caps::builder("video/quicktime").field("variant", &"iso").build(),
+                "video/x-h264",
+            ),
+            RecordingProfile::Vp9WebM => (gst::Caps::new_simple("video/webm", &[]), "video/x-vp9"),
+        };
+
+        let video_profile = gst_pbutils::EncodingVideoProfile::builder(&gst::Caps::new_simple(video, &[]))
+            .presence(0)
+            .build();
+
+        gst_pbutils::EncodingContainerProfile::builder(&container)
+            .name("record")
+            .add_profile(&video_profile)
+            .build()
+    }
+}
+
+/// The elements spliced in while recording, kept so [`CamProvider::stop_recording`]
+/// can flush and tear them back down.
+struct RecordingBranch {
+    tee: gst::Element,
+    queue: gst::Element,
+    encodebin: gst::Element,
+    filesink: gst::Element,
+    tee_src_pad: gst::Pad,
+}
+
+/// Liveness of the camera feed, surfaced through [`CamProvider::connection_state`]
+/// so the host app can show "camera lost / reconnecting".
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionState {
+    Connected,
+    Reconnecting,
+    Disconnected,
 }
 
 pub struct CamProvider {
     name: String,
     video_buffer: Arc<Mutex<Buffer>>,
     pipeline: gst::Element,
+    source: gst::Element,
+    recording: Option<RecordingBranch>,
+    connection_state: Arc<Mutex<ConnectionState>>,
 }
 
 impl CamProvider {
+    /// Enumerate the cameras available on this machine.
+    ///
+    /// Uses a `DeviceMonitor` filtered on `Video/Source` so the same code path
+    /// works on every platform GStreamer supports, and parses each device's
+    /// caps into a [`CamCaps`] list so a UI can populate a picker and validate a
+    /// requested resolution before a pipeline is ever built.
+    pub fn list_devices() -> Result<Vec<CamDevice>> {
+        gst::init().context("Failed to initialize the gstreamer library")?;
+
+        let monitor = gst::DeviceMonitor::new();
+        monitor
+            .add_filter(Some("Video/Source"), None)
+            .context("Failed to add a Video/Source filter to the device monitor")?;
+
+        monitor
+            .start()
+            .map_err(|_| anyhow::anyhow!("Failed to start the gstreamer device monitor"))?;
+
+        let devices = monitor.get_devices();
+
+        monitor.stop();
+
+        Ok(devices
+            .iter()
+            .map(|device| {
+                let display_name = device.get_display_name().to_string();
+
+                let path = device
+                    .get_properties()
+                    .and_then(|properties| {
+                        properties
+                            .get_optional::<String>("device.path")
+                            .ok()
+                            .flatten()
+                            .or_else(|| properties.get_optional::<String>("object.path").ok().flatten())
+                    })
+                    .unwrap_or_else(|| display_name.clone());
+
+                let caps = device
+                    .get_caps()
+                    .map(|caps| parse_device_caps(&caps))
+                    .unwrap_or_default();
+
+                CamDevice {
+                    display_name,
+                    path,
+                    caps,
+                }
+            })
+            .collect())
+    }
+
     pub fn new(path: &str, name: String, resolution: (usize, usize)) -> Result<Self> {
         gst::init().expect("Failed to initialize the gstreamer library");
 
@@ -40,23 +186,39 @@ impl CamProvider {
         }));
 
         let src = if cfg!(target_os = "linux") {
-            format!("v4l2src device={:}", path)
+            format!("v4l2src name=source device={:}", path)
         } else {
-            "autovideosrc".to_owned()
+            "autovideosrc name=source".to_owned()
+        };
+
+        // Many USB webcams only emit MJPEG; decode it back to raw frames when
+        // the device negotiates `image/jpeg` so the rest of the pipeline keeps
+        // seeing `video/x-raw`. Other devices already deliver raw YUY2/GRAY8/RGB
+        // which the `new_sample` callback now unpacks directly.
+        let decode = if device_is_mjpeg(path) {
+            " ! image/jpeg ! jpegdec"
+        } else {
+            ""
         };
 
         let pipeline_string = format!(
-            "{:} ! videoconvert ! videoscale ! video/x-raw,format=RGB,format=RGBA,format=BGR,format=BGRA,width={:},height={:} ! videoflip method=vertical-flip ! appsink name=appsink async=true sync=false",
-            src, resolution.0, resolution.1
+            "{:}{:} ! videoconvert name=videoconvert ! videoscale ! video/x-raw,format={{ RGB, RGBA, BGR, BGRA, YUY2, UYVY, GRAY8 }},width={:},height={:} ! videoflip method=vertical-flip ! appsink name=appsink async=true sync=false",
+            src, decode, resolution.0, resolution.1
         );
 
         let pipeline =
             gst::parse_launch(&pipeline_string).context("Failed to build gstreamer pipeline")?;
 
-        let sink = pipeline
+        let bin = pipeline
             .clone()
             .dynamic_cast::<gst::Bin>()
-            .expect("Failed to cast the gstreamer pipeline as a gst::Bin element")
+            .expect("Failed to cast the gstreamer pipeline as a gst::Bin element");
+
+        let source = bin
+            .get_by_name("source")
+            .expect("Failed to retrieve source from gstreamer pipeline.");
+
+        let sink = bin
             .get_by_name("appsink")
             .expect("Failed to retrieve sink from gstreamer pipeline.");
 
@@ -64,88 +226,719 @@ impl CamProvider {
             .dynamic_cast::<gst_app::AppSink>()
             .expect("The sink defined in the pipeline is not an appsink");
 
+        install_video_callback(&appsink, video_buffer.clone());
+
+        pipeline
+            .set_state(State::Playing)
+            .context("Failed to start gstreamer pipeline")?;
+
+        let connection_state = Arc::new(Mutex::new(ConnectionState::Connected));
+
+        spawn_bus_watch(
+            pipeline.clone(),
+            source.clone(),
+            video_buffer.clone(),
+            connection_state.clone(),
+            path.to_owned(),
+            false,
+        );
+
+        Ok(Self {
+            name,
+            video_buffer,
+            pipeline,
+            source,
+            recording: None,
+            connection_state,
+        })
+    }
+
+    /// Build a provider backed by a network or file URI instead of a local
+    /// capture device.
+    ///
+    /// Accepts `rtsp://`, `http(s)://` and `file://` sources through
+    /// `uridecodebin`, whose video pad only appears after preroll, so the
+    /// decoder is linked to the conversion chain from a `pad-added` handler.
+    /// The vertical flip and RGB conversion are kept identical to the capture
+    /// path; when `loop_playback` is set (typically for `file://` clips) the
+    /// bus watch seeks back to the start on EOS so the layer plays continuously.
+    pub fn from_uri(
+        uri: &str,
+        name: String,
+        resolution: (usize, usize),
+        loop_playback: bool,
+    ) -> Result<Self> {
+        gst::init().expect("Failed to initialize the gstreamer library");
+
+        let video_buffer = Arc::new(Mutex::new(Buffer {
+            dimensions: vec![resolution.0, resolution.1, 3],
+            data: None,
+        }));
+
+        let pipeline = gst::Pipeline::new(None);
+
+        let uridecodebin = gst::ElementFactory::make("uridecodebin", Some("source"))
+            .context("Failed to create uridecodebin")?;
+        uridecodebin
+            .set_property("uri", &uri)
+            .context("Failed to set the source uri")?;
+
+        // The conversion tail mirrors the capture pipeline exactly so the
+        // buffers handed to shaders are indistinguishable between sources.
+        let tail_string = format!(
+            "videoconvert name=videoconvert ! videoscale ! video/x-raw,format={{ RGB, RGBA, BGR, BGRA, YUY2, UYVY, GRAY8 }},width={:},height={:} ! videoflip method=vertical-flip ! appsink name=appsink async=true sync=false",
+            resolution.0, resolution.1
+        );
+        let tail = gst::parse_bin_from_description(&tail_string, true)
+            .context("Failed to build the conversion tail")?;
+
+        pipeline
+            .add_many(&[&uridecodebin, tail.upcast_ref()])
+            .context("Failed to assemble the uri pipeline")?;
+
+        // `uridecodebin` exposes its video pad only after preroll; link it to
+        // the conversion tail's ghost sink pad once it shows up.
         {
-            let video_buffer = video_buffer.clone();
-            appsink.set_callbacks(
-                gst_app::AppSinkCallbacks::builder()
-                    .new_sample(move |appsink| {
-                        let sample = match appsink.pull_sample() {
-                            Err(e) => {
-                                println!("{:}", e);
-                                return Err(gst::FlowError::Eos);
-                            }
-                            Ok(sample) => sample,
-                        };
-
-                        let sample_caps = if let Some(sample_caps) = sample.get_caps() {
-                            sample_caps
-                        } else {
-                            return Err(gst::FlowError::Error);
-                        };
-
-                        let video_info = if let Ok(video_info) = gst_video::VideoInfo::from_caps(sample_caps) {
-                            video_info
-                        } else {
-                            return Err(gst::FlowError::Error);
-                        };
-
-                        let buffer = if let Some(buffer) = sample.get_buffer() {
-                            buffer
-                        } else {
-                            return Err(gst::FlowError::Error);
-                        };
-
-                        let map = if let Ok(map) = buffer.map_readable() {
-                            map
-                        } else {
-                            return Err(gst::FlowError::Error);
-                        };
-
-                        let samples = map.as_slice().to_vec();
-                        let format = match video_info.format() {
-                            gst_video::VideoFormat::Rgb => TextureFormat::RGBU8,
-                            gst_video::VideoFormat::Rgba => TextureFormat::RGBAU8,
-                            gst_video::VideoFormat::Bgr => TextureFormat::BGRU8,
-                            gst_video::VideoFormat::Bgra => TextureFormat::BGRAU8,
-                            unsupported_format => {
-                                eprintln!("Unsupported format: {:?}", unsupported_format);
-                                return Err(gst::FlowError::Error);
-                            }
-                        };
-
-                        let image_buffer = match format {
-                            TextureFormat::RGBU8 => image::DynamicImage::ImageRgb8(image::RgbImage::from_raw(video_info.width(), video_info.height(), samples).unwrap()).into_rgb8(),
-                            TextureFormat::RGBAU8 => image::DynamicImage::ImageRgba8(image::RgbaImage::from_raw(video_info.width(), video_info.height(), samples).unwrap()).into_rgb8(),
-                            TextureFormat::BGRU8 => image::DynamicImage::ImageBgr8(BgrImage::from_raw(video_info.width(), video_info.height(), samples).unwrap()).into_rgb8(),
-                            TextureFormat::BGRAU8 => image::DynamicImage::ImageBgra8(BgraImage::from_raw(video_info.width(), video_info.height(), samples).unwrap()).into_rgb8(),
-                        };
-
-                        match video_buffer.lock() {
-                            Ok(mut video_buffer) => {
-                                video_buffer.data = Some(image_buffer.into_vec());
-                                video_buffer.dimensions = vec![video_info.width() as usize, video_info.height() as usize, 3];
-                            }
-                            Err(e) => {
-                                eprintln!("Could not lock video buffer, did the main thread panic? \n{:?}", e);
-                                return Err(FlowError::Error);
-                            }
-                        }
+            let tail = tail.clone();
+            uridecodebin.connect_pad_added(move |_, src_pad| {
+                let is_video = src_pad
+                    .get_current_caps()
+                    .and_then(|caps| caps.get_structure(0).map(|s| s.get_name().starts_with("video/")))
+                    .unwrap_or(false);
 
-                        Ok(gst::FlowSuccess::Ok)
-                    })
-                    .build(),
-            );
+                if !is_video {
+                    return;
+                }
+
+                if let Some(sink_pad) = tail.get_static_pad("sink") {
+                    if sink_pad.is_linked() {
+                        return;
+                    }
+
+                    if src_pad.link(&sink_pad).is_err() {
+                        eprintln!("Failed to link uridecodebin to the conversion tail");
+                    }
+                }
+            });
         }
 
+        let appsink = tail
+            .get_by_name("appsink")
+            .expect("Failed to retrieve sink from gstreamer pipeline.")
+            .dynamic_cast::<gst_app::AppSink>()
+            .expect("The sink defined in the pipeline is not an appsink");
+
+        install_video_callback(&appsink, video_buffer.clone());
+
+        let pipeline = pipeline.upcast::<gst::Element>();
+
+        let source = pipeline
+            .clone()
+            .dynamic_cast::<gst::Bin>()
+            .expect("Failed to cast the gstreamer pipeline as a gst::Bin element")
+            .get_by_name("source")
+            .expect("Failed to retrieve source from gstreamer pipeline.");
+
         pipeline
             .set_state(State::Playing)
             .context("Failed to start gstreamer pipeline")?;
 
+        let connection_state = Arc::new(Mutex::new(ConnectionState::Connected));
+
+        spawn_bus_watch(
+            pipeline.clone(),
+            source.clone(),
+            video_buffer.clone(),
+            connection_state.clone(),
+            uri.to_owned(),
+            loop_playback,
+        );
+
         Ok(Self {
             name,
             video_buffer,
             pipeline,
+            source,
+            recording: None,
+            connection_state,
+        })
+    }
+
+    /// Current liveness of the feed. `Reconnecting` means the bus watch is
+    /// trying to recover the device; `Disconnected` means every attempt failed.
+    /// In both states the last frame in `get` is stale and has been cleared.
+    pub fn connection_state(&self) -> ConnectionState {
+        self.connection_state
+            .lock()
+            .map(|state| *state)
+            .unwrap_or(ConnectionState::Disconnected)
+    }
+
+    /// Start archiving the live feed to `path`, encoded with `profile`.
+    ///
+    /// A `tee` is spliced in right after `videoconvert`: one branch carries on
+    /// to the existing `appsink`, the other runs `queue ! encodebin ! filesink`
+    /// so capture is never interrupted. The added elements are synced to the
+    /// running pipeline's state so recording begins immediately.
+    pub fn start_recording(&mut self, path: &str, profile: RecordingProfile) -> Result<()> {
+        if self.recording.is_some() {
+            return Ok(());
+        }
+
+        let bin = self
+            .pipeline
+            .clone()
+            .dynamic_cast::<gst::Bin>()
+            .expect("Failed to cast the gstreamer pipeline as a gst::Bin element");
+
+        let videoconvert = bin
+            .get_by_name("videoconvert")
+            .context("Failed to retrieve videoconvert from gstreamer pipeline")?;
+
+        let tee = gst::ElementFactory::make("tee", None).context("Failed to create tee")?;
+        let queue = gst::ElementFactory::make("queue", None).context("Failed to create queue")?;
+        let encodebin =
+            gst::ElementFactory::make("encodebin", None).context("Failed to create encodebin")?;
+        let filesink =
+            gst::ElementFactory::make("filesink", None).context("Failed to create filesink")?;
+
+        encodebin
+            .set_property("profile", &profile.to_container_profile())
+            .context("Failed to configure the encoding profile")?;
+        filesink
+            .set_property("location", &path)
+            .context("Failed to set the recording location")?;
+
+        bin.add_many(&[&tee, &queue, &encodebin, &filesink])
+            .context("Failed to add the recording branch to the pipeline")?;
+
+        // The pad feeding the existing downstream chain (appsink); we splice the
+        // tee between `videoconvert` and this pad.
+        let videoconvert_src = videoconvert
+            .get_static_pad("src")
+            .context("videoconvert has no src pad")?;
+        let downstream = videoconvert_src
+            .get_peer()
+            .context("videoconvert is not linked to a downstream element")?;
+
+        // Build the recording branch up front: tee -> queue -> encodebin ->
+        // filesink. The tee isn't receiving data yet, so this is safe to link
+        // before the splice.
+        let tee_src_pad = tee
+            .get_request_pad("src_%u")
+            .context("Failed to request a tee pad for the recording branch")?;
+        let queue_sink = queue
+            .get_static_pad("sink")
+            .context("Failed to retrieve the queue sink pad")?;
+        tee_src_pad
+            .link(&queue_sink)
+            .map_err(|_| anyhow::anyhow!("Failed to link the tee recording branch"))?;
+
+        gst::Element::link_many(&[&queue, &encodebin, &filesink])
+            .context("Failed to link the recording branch")?;
+
+        for element in &[&tee, &queue, &encodebin, &filesink] {
+            element
+                .sync_state_with_parent()
+                .context("Failed to sync the recording branch state")?;
+        }
+
+        // Swapping links on a live pipeline races in-flight buffers, so perform
+        // the relink from a blocking IDLE probe: GStreamer calls us only when no
+        // buffer is crossing `videoconvert`'s src pad, and returning `Remove`
+        // unblocks the pad once the tee is spliced in.
+        let tee_probe = tee.clone();
+        let downstream_probe = downstream.clone();
+        videoconvert_src.add_probe(gst::PadProbeType::IDLE, move |pad, _info| {
+            let _ = pad.unlink(&downstream_probe);
+
+            if let Some(sink_pad) = tee_probe.get_static_pad("sink") {
+                let _ = pad.link(&sink_pad);
+            }
+
+            if let Some(tee_preview) = tee_probe.get_request_pad("src_%u") {
+                let _ = tee_preview.link(&downstream_probe);
+            }
+
+            gst::PadProbeReturn::Remove
+        });
+
+        self.recording = Some(RecordingBranch {
+            tee,
+            queue,
+            encodebin,
+            filesink,
+            tee_src_pad,
+        });
+
+        Ok(())
+    }
+
+    /// Stop an in-progress recording and finalize the file.
+    ///
+    /// An EOS is injected into the recording branch so `encodebin`/muxer flush
+    /// their trailers before the branch is unlinked and removed; the preview
+    /// branch feeding `appsink` is left untouched.
+    pub fn stop_recording(&mut self) -> Result<()> {
+        let branch = match self.recording.take() {
+            Some(branch) => branch,
+            None => return Ok(()),
+        };
+
+        // Watch for EOS arriving at the very end of the branch so we only tear
+        // down once the muxer has actually written its trailer — `queue` runs
+        // its own streaming thread, so EOS is asynchronous to this call.
+        let filesink_sink = branch
+            .filesink
+            .get_static_pad("sink")
+            .context("Failed to retrieve the filesink sink pad")?;
+        let (eos_tx, eos_rx) = std::sync::mpsc::channel();
+        filesink_sink.add_probe(gst::PadProbeType::EVENT_DOWNSTREAM, move |_pad, info| {
+            if let Some(gst::PadProbeData::Event(ref event)) = info.data {
+                if event.get_type() == gst::EventType::Eos {
+                    let _ = eos_tx.send(());
+                }
+            }
+            gst::PadProbeReturn::Ok
+        });
+
+        // Flush the encoder/muxer so the container trailer is written, then
+        // block until that EOS has propagated all the way to the filesink.
+        let queue_sink = branch
+            .queue
+            .get_static_pad("sink")
+            .context("Failed to retrieve the queue sink pad")?;
+        queue_sink.send_event(gst::event::Eos::new());
+
+        if eos_rx.recv_timeout(Duration::from_secs(5)).is_err() {
+            eprintln!("Timed out waiting for recording EOS; the file may be truncated");
+        }
+
+        let bin = self
+            .pipeline
+            .clone()
+            .dynamic_cast::<gst::Bin>()
+            .expect("Failed to cast the gstreamer pipeline as a gst::Bin element");
+
+        branch.tee.release_request_pad(&branch.tee_src_pad);
+
+        for element in &[&branch.queue, &branch.encodebin, &branch.filesink] {
+            element
+                .set_state(State::Null)
+                .context("Failed to stop a recording branch element")?;
+        }
+
+        bin.remove_many(&[&branch.queue, &branch.encodebin, &branch.filesink])
+            .context("Failed to remove the recording branch from the pipeline")?;
+
+        Ok(())
+    }
+}
+
+/// Install the shared sample callback that unpacks each negotiated frame into
+/// the RGB `video_buffer` (GRAY8 is broadcast to RGB); the vertical flip is
+/// done upstream by `videoflip`. Both the local-camera and URI sources route
+/// their `appsink` through this so downstream consumers see identical buffers.
+fn install_video_callback(appsink: &gst_app::AppSink, video_buffer: Arc<Mutex<Buffer>>) {
+    appsink.set_callbacks(
+        gst_app::AppSinkCallbacks::builder()
+            .new_sample(move |appsink| {
+                let sample = match appsink.pull_sample() {
+                    Err(e) => {
+                        println!("{:}", e);
+                        return Err(gst::FlowError::Eos);
+                    }
+                    Ok(sample) => sample,
+                };
+
+                let sample_caps = if let Some(sample_caps) = sample.get_caps() {
+                    sample_caps
+                } else {
+                    return Err(gst::FlowError::Error);
+                };
+
+                let video_info = if let Ok(video_info) = gst_video::VideoInfo::from_caps(sample_caps) {
+                    video_info
+                } else {
+                    return Err(gst::FlowError::Error);
+                };
+
+                let buffer = if let Some(buffer) = sample.get_buffer() {
+                    buffer
+                } else {
+                    return Err(gst::FlowError::Error);
+                };
+
+                let map = if let Ok(map) = buffer.map_readable() {
+                    map
+                } else {
+                    return Err(gst::FlowError::Error);
+                };
+
+                let samples = map.as_slice().to_vec();
+                let width = video_info.width();
+                let height = video_info.height();
+                let format = match video_info.format() {
+                    gst_video::VideoFormat::Rgb => TextureFormat::RGBU8,
+                    gst_video::VideoFormat::Rgba => TextureFormat::RGBAU8,
+                    gst_video::VideoFormat::Bgr => TextureFormat::BGRU8,
+                    gst_video::VideoFormat::Bgra => TextureFormat::BGRAU8,
+                    gst_video::VideoFormat::Yuy2 => TextureFormat::Yuy2,
+                    gst_video::VideoFormat::Uyvy => TextureFormat::Uyvy,
+                    gst_video::VideoFormat::Gray8 => TextureFormat::Gray8,
+                    unsupported_format => {
+                        eprintln!("Unsupported format: {:?}", unsupported_format);
+                        return Err(gst::FlowError::Error);
+                    }
+                };
+
+                // `channels` tracks the layout we hand downstream: every path,
+                // GRAY8 included, is unpacked to three-channel RGB.
+                let (data, channels) = match format {
+                    TextureFormat::RGBU8 => (image::DynamicImage::ImageRgb8(image::RgbImage::from_raw(width, height, samples).unwrap()).into_rgb8().into_vec(), 3),
+                    TextureFormat::RGBAU8 => (image::DynamicImage::ImageRgba8(image::RgbaImage::from_raw(width, height, samples).unwrap()).into_rgb8().into_vec(), 3),
+                    TextureFormat::BGRU8 => (image::DynamicImage::ImageBgr8(BgrImage::from_raw(width, height, samples).unwrap()).into_rgb8().into_vec(), 3),
+                    TextureFormat::BGRAU8 => (image::DynamicImage::ImageBgra8(BgraImage::from_raw(width, height, samples).unwrap()).into_rgb8().into_vec(), 3),
+                    TextureFormat::Yuy2 => (yuyv_to_rgb(&samples, width, height, video_info.stride()[0] as usize, false), 3),
+                    TextureFormat::Uyvy => (yuyv_to_rgb(&samples, width, height, video_info.stride()[0] as usize, true), 3),
+                    // The request asks for a single-channel `[w,h,1]` texture, but
+                    // `DataHolder::Texture` carries only `((w,h), bytes)` and `get`
+                    // drops `dimensions[2]` — a 1-byte/pixel buffer would be read as
+                    // 3-byte RGB downstream. We therefore honour the "broadcast to
+                    // RGB" wording instead, replicating luma across R/G/B so the
+                    // 3-byte/pixel contract shared with every other format holds.
+                    TextureFormat::Gray8 => (gray8_to_rgb(&samples, width, height, video_info.stride()[0] as usize), 3),
+                };
+
+                match video_buffer.lock() {
+                    Ok(mut video_buffer) => {
+                        video_buffer.data = Some(data);
+                        video_buffer.dimensions = vec![width as usize, height as usize, channels];
+                    }
+                    Err(e) => {
+                        eprintln!("Could not lock video buffer, did the main thread panic? \n{:?}", e);
+                        return Err(FlowError::Error);
+                    }
+                }
+
+                Ok(gst::FlowSuccess::Ok)
+            })
+            .build(),
+    );
+}
+
+/// Maximum number of consecutive reconnect attempts before the feed is left in
+/// the `Disconnected` state until the pipeline is restarted.
+const MAX_RECONNECT_ATTEMPTS: u32 = 6;
+
+/// Watch the pipeline bus on a background thread, driving recovery when the
+/// camera disappears.
+///
+/// On a source `Error`/`Eos` the feed's last frame is cleared so downstream
+/// shaders can react to the loss of signal, and a bounded exponential-backoff
+/// reconnect is attempted: the pipeline is cycled through `Null`, the device is
+/// re-resolved through the enumeration logic, and the pipeline is returned to
+/// `Playing`.
+fn spawn_bus_watch(
+    pipeline: gst::Element,
+    source: gst::Element,
+    video_buffer: Arc<Mutex<Buffer>>,
+    connection_state: Arc<Mutex<ConnectionState>>,
+    path: String,
+    loop_playback: bool,
+) {
+    let bus = match pipeline.get_bus() {
+        Some(bus) => bus,
+        None => {
+            eprintln!("Pipeline has no bus; camera recovery is disabled");
+            return;
+        }
+    };
+
+    thread::spawn(move || {
+        let set_state = |state: ConnectionState| {
+            if let Ok(mut current) = connection_state.lock() {
+                *current = state;
+            }
+        };
+
+        loop {
+            let message = match bus.timed_pop(gst::ClockTime::from_seconds(1)) {
+                Some(message) => message,
+                None => continue,
+            };
+
+            use gst::MessageView;
+            match message.view() {
+                MessageView::StateChanged(state_changed) => {
+                    if message.get_src().as_ref() == Some(pipeline.upcast_ref())
+                        && state_changed.get_current() == State::Playing
+                    {
+                        set_state(ConnectionState::Connected);
+                    }
+                }
+                MessageView::Error(err) => {
+                    eprintln!("Camera pipeline error: {:}", err.get_error());
+
+                    if !reconnect(&pipeline, &source, &video_buffer, &connection_state, &path) {
+                        set_state(ConnectionState::Disconnected);
+                    }
+                }
+                MessageView::Eos(..) => {
+                    // A looping file source seeks back to the start instead of
+                    // treating the end of the clip as a lost connection.
+                    if loop_playback {
+                        if pipeline
+                            .seek_simple(
+                                gst::SeekFlags::FLUSH | gst::SeekFlags::KEY_UNIT,
+                                gst::ClockTime::from_seconds(0),
+                            )
+                            .is_err()
+                        {
+                            eprintln!("Failed to loop the video source");
+                        }
+                    } else if !reconnect(&pipeline, &source, &video_buffer, &connection_state, &path) {
+                        set_state(ConnectionState::Disconnected);
+                    }
+                }
+                _ => {}
+            }
+        }
+    });
+}
+
+/// Attempt a bounded exponential-backoff reconnect of a lost camera.
+///
+/// Clears the stale frame, cycles the pipeline to `Null`, waits for the device
+/// to reappear in the enumeration, and brings the pipeline back to `Playing`.
+/// Returns `true` once the pipeline is playing again, `false` if every attempt
+/// was exhausted.
+fn reconnect(
+    pipeline: &gst::Element,
+    source: &gst::Element,
+    video_buffer: &Arc<Mutex<Buffer>>,
+    connection_state: &Arc<Mutex<ConnectionState>>,
+    path: &str,
+) -> bool {
+    if let Ok(mut state) = connection_state.lock() {
+        *state = ConnectionState::Reconnecting;
+    }
+
+    // Drop the last frame so shaders see the absence of signal immediately.
+    if let Ok(mut video_buffer) = video_buffer.lock() {
+        video_buffer.data = None;
+    }
+
+    let _ = pipeline.set_state(State::Null);
+
+    // Whether the source takes a `device` path we can re-point on recovery
+    // (`v4l2src` does, `autovideosrc`/`uridecodebin` do not).
+    let has_device = source.find_property("device").is_some();
+
+    let mut delay = Duration::from_millis(500);
+    for _ in 0..MAX_RECONNECT_ATTEMPTS {
+        thread::sleep(delay);
+
+        // Re-resolve the device through the same enumeration used to open it.
+        // A USB camera often re-enumerates at a different `/dev/videoN`, so we
+        // accept the original path if it is still there and otherwise fall back
+        // to the first available camera, re-pointing the source at it before
+        // going back to `Playing`. Sources without a `device` property simply
+        // retry the existing pipeline.
+        let resolved = if has_device {
+            match CamProvider::list_devices() {
+                Ok(devices) => {
+                    if devices.iter().any(|device| device.path == path) {
+                        Some(path.to_owned())
+                    } else {
+                        devices.into_iter().next().map(|device| device.path)
+                    }
+                }
+                Err(_) => None,
+            }
+        } else {
+            Some(path.to_owned())
+        };
+
+        let resolved = match resolved {
+            Some(resolved) => resolved,
+            None => {
+                delay = (delay * 2).min(Duration::from_secs(16));
+                continue;
+            }
+        };
+
+        if has_device {
+            let _ = source.set_property("device", &resolved);
+        }
+
+        if pipeline.set_state(State::Playing).is_ok() {
+            return true;
+        }
+
+        delay = (delay * 2).min(Duration::from_secs(16));
+    }
+
+    let _ = pipeline.set_state(State::Null);
+    false
+}
+
+/// Whether the device at `path` natively emits MJPEG, so [`CamProvider::new`]
+/// knows to splice a `jpegdec` into the pipeline.
+///
+/// Falls back to `false` (raw capture) if the device cannot be enumerated, in
+/// which case the capsfilter simply negotiates whatever raw format fits.
+fn device_is_mjpeg(path: &str) -> bool {
+    CamProvider::list_devices()
+        .map(|devices| {
+            devices
+                .iter()
+                .find(|device| device.path == path)
+                .map(|device| device.caps.iter().any(|caps| caps.format == "image/jpeg"))
+                .unwrap_or(false)
+        })
+        .unwrap_or(false)
+}
+
+/// Convert a packed 4:2:2 buffer (`YUY2`, or `UYVY` when `swapped`) into tightly
+/// packed RGB, using the BT.601 coefficients common to USB webcams.
+///
+/// Each four-byte macro-pixel carries two luma samples that share one chroma
+/// pair, so the output is three bytes per source pixel. Rows are read through
+/// the plane `stride` reported by `VideoInfo`, since GStreamer rounds each row
+/// up for alignment and the trailing padding must be skipped.
+fn yuyv_to_rgb(samples: &[u8], width: u32, height: u32, stride: usize, swapped: bool) -> Vec<u8> {
+    let width = width as usize;
+    let mut rgb = Vec::with_capacity(width * height as usize * 3);
+
+    for row in samples.chunks_exact(stride).take(height as usize) {
+        for macro_pixel in row[..width * 2].chunks_exact(4) {
+            let (y0, u, y1, v) = if swapped {
+                (macro_pixel[1], macro_pixel[0], macro_pixel[3], macro_pixel[2])
+            } else {
+                (macro_pixel[0], macro_pixel[1], macro_pixel[2], macro_pixel[3])
+            };
+
+            push_ycbcr(&mut rgb, y0, u, v);
+            push_ycbcr(&mut rgb, y1, u, v);
+        }
+    }
+
+    rgb
+}
+
+/// Broadcast a single-channel GRAY8 buffer to tightly packed RGB by replicating
+/// each luma sample across the three colour channels.
+///
+/// Like [`yuyv_to_rgb`], rows are read through the plane `stride` so the
+/// row-alignment padding GStreamer inserts does not shear the image.
+fn gray8_to_rgb(samples: &[u8], width: u32, height: u32, stride: usize) -> Vec<u8> {
+    let width = width as usize;
+    let mut rgb = Vec::with_capacity(width * height as usize * 3);
+
+    for row in samples.chunks_exact(stride).take(height as usize) {
+        for &luma in &row[..width] {
+            rgb.push(luma);
+            rgb.push(luma);
+            rgb.push(luma);
+        }
+    }
+
+    rgb
+}
+
+/// Decode one BT.601 Y'CbCr sample into clamped RGB bytes.
+fn push_ycbcr(rgb: &mut Vec<u8>, y: u8, u: u8, v: u8) {
+    let c = y as f32 - 16.0;
+    let d = u as f32 - 128.0;
+    let e = v as f32 - 128.0;
+
+    let clamp = |value: f32| value.max(0.0).min(255.0) as u8;
+
+    rgb.push(clamp(1.164 * c + 1.596 * e));
+    rgb.push(clamp(1.164 * c - 0.392 * d - 0.813 * e));
+    rgb.push(clamp(1.164 * c + 2.017 * d));
+}
+
+/// Map a VJ-facing control name to the corresponding v4l2 control id exposed
+/// through `v4l2src`'s `extra-controls` structure.
+///
+/// Returns `None` for names this source does not know about so the caller can
+/// fall back to a direct element property on other platforms.
+fn v4l2_control_name(property: &str) -> Option<&'static str> {
+    match property {
+        "brightness" => Some("brightness"),
+        "contrast" => Some("contrast"),
+        "saturation" => Some("saturation"),
+        "exposure" | "exposure_absolute" => Some("exposure_absolute"),
+        "gain" => Some("gain"),
+        "focus" | "focus_absolute" => Some("focus_absolute"),
+        "white_balance_temperature" => Some("white_balance_temperature"),
+        _ => None,
+    }
+}
+
+/// Extract the `(format, width, height, framerates)` tuples advertised by a
+/// device's caps into the crate's [`CamCaps`] representation.
+///
+/// Both integer and range-typed width/height fields are handled. A single
+/// framerate fraction or a list of fractions is flattened into the reported
+/// fractions; a framerate given as a range yields an empty list.
+fn parse_device_caps(caps: &gst::Caps) -> Vec<CamCaps> {
+    caps.iter()
+        .map(|structure| {
+            let format = structure
+                .get_optional::<String>("format")
+                .ok()
+                .flatten()
+                .unwrap_or_else(|| structure.get_name().to_string());
+
+            let width = read_int_range(structure, "width");
+            let height = read_int_range(structure, "height");
+
+            let framerates = match structure.get_optional::<gst::Fraction>("framerate") {
+                Ok(Some(fraction)) => {
+                    let (numer, denom) = fraction.into();
+                    vec![(numer, denom)]
+                }
+                _ => read_fraction_list(structure, "framerate"),
+            };
+
+            CamCaps {
+                format,
+                width,
+                height,
+                framerates,
+            }
         })
+        .collect()
+}
+
+/// Read an integer field that may be stored either as a plain `i32` or as an
+/// `IntRange`, returning it as an inclusive `(min, max)` pair.
+fn read_int_range(structure: &gst::StructureRef, field: &str) -> (usize, usize) {
+    if let Ok(Some(value)) = structure.get_optional::<i32>(field) {
+        let value = value.max(0) as usize;
+        return (value, value);
+    }
+
+    if let Ok(Some(range)) = structure.get_optional::<gst::IntRange<i32>>(field) {
+        return (range.min().max(0) as usize, range.max().max(0) as usize);
+    }
+
+    (0, 0)
+}
+
+/// Flatten a `list` of framerate fractions into raw `(numer, denom)` pairs,
+/// ignoring any entry that is not itself a fraction.
+fn read_fraction_list(structure: &gst::StructureRef, field: &str) -> Vec<(i32, i32)> {
+    match structure.get_optional::<gst::List>(field) {
+        Ok(Some(list)) => list
+            .iter()
+            .filter_map(|value| value.get::<gst::Fraction>().ok().flatten())
+            .map(|fraction| fraction.into())
+            .collect(),
+        _ => Vec::new(),
     }
 }
 
@@ -166,8 +959,63 @@ impl InputProvider for CamProvider {
         vec![self.name.clone()]
     }
 
-    fn set_property(&mut self, property: &str, _value: &DataHolder) {
-        eprintln!("Set_property unimplemented for {:}", property);
+    fn set_property(&mut self, property: &str, value: &DataHolder) {
+        // Controls arrive as scalars from the patch; normalise them to the
+        // integer values the underlying drivers expect.
+        let value = match value {
+            DataHolder::Int(value) => *value,
+            DataHolder::Float(value) => value.round() as i32,
+            other => {
+                eprintln!("Cannot apply {:?} to camera control {:}", other, property);
+                return;
+            }
+        };
+
+        if cfg!(target_os = "linux") {
+            if let Some(control) = v4l2_control_name(property) {
+                // `v4l2src` exposes driver controls as fields of its
+                // `extra-controls` structure; merge the new value in rather
+                // than clobbering any previously applied control.
+                let mut controls = self
+                    .source
+                    .get_property("extra-controls")
+                    .ok()
+                    .and_then(|value| value.get::<gst::Structure>().ok().flatten())
+                    .unwrap_or_else(|| gst::Structure::new_empty("extra-controls"));
+
+                controls.set(control, &value);
+
+                if let Err(e) = self.source.set_property("extra-controls", &controls) {
+                    eprintln!("Failed to set camera control {:}: {:?}", property, e);
+                }
+
+                return;
+            }
+        }
+
+        // On other platforms `autovideosrc` wraps the real source; resolve the
+        // child element that actually carries the property at runtime.
+        let target = self
+            .source
+            .clone()
+            .dynamic_cast::<gst::Bin>()
+            .ok()
+            .and_then(|bin| {
+                bin.iterate_recurse()
+                    .into_iter()
+                    .filter_map(|element| element.ok())
+                    .find(|element| element.find_property(property).is_some())
+            })
+            .unwrap_or_else(|| self.source.clone());
+
+        if target.find_property(property).is_none() {
+            eprintln!("Camera control {:} is not supported by this source", property);
+            return;
+        }
+
+        if let Err(e) = target.set_property(property, &value) {
+            eprintln!("Failed to set camera control {:}: {:?}", property, e);
+        }
     }
 
     fn get(&mut self, uniform_name: &str, invalidate: bool) -> Option<DataHolder> {